@@ -5,7 +5,7 @@
 //! [`Integer`]: ../num_integer/trait.Integer.html
 //! [`BitOps`]: ./trait.BitOps.html
 
-use std::ops::{BitAnd, Shl, Shr};
+use std::ops::{BitAnd, BitOr, BitXor, Shl, Shr};
 
 use num_integer::Integer;
 
@@ -28,7 +28,14 @@ use num_integer::Integer;
 ///
 /// [`Integer`]: ../num_integer/trait.Integer.html
 pub trait BitOps:
-    Copy + Integer + BitAnd<Output = Self> + Shl<Output = Self> + Shr<Output = Self> + From<u8>
+    Copy
+    + Integer
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Shl<Output = Self>
+    + Shr<Output = Self>
+    + From<u8>
 {
     /// Returns whether this number only has one bit set.
     ///
@@ -60,7 +67,7 @@ pub trait BitOps:
     /// ```
     #[inline]
     fn is_bit_set(&self, bit: u8) -> bool {
-        self.is_flag_set(Self::one() << Self::from(bit))
+        self.is_flag_set(Self::single_bit(bit))
     }
 
     /// Returns whether the given flag is set.
@@ -95,14 +102,409 @@ pub trait BitOps:
     fn bits_as_int(&self, bit: u8, count: u8) -> Self {
         (*self >> Self::from(bit)) & ((Self::one() << Self::from(count)) - Self::one())
     }
+
+    /// Returns a number with the `count`-wide field starting at `bit` replaced by the low
+    /// `count` bits of `value`. This is the inverse of [`bits_as_int`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit` or `bit + count` is greater than the number of bits in this Integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitops::BitOps;
+    ///
+    /// assert_eq!(0xab000.with_bits_set(12, 8, 0xcd), 0xcd000);
+    /// ```
+    ///
+    /// [`bits_as_int`]: Self::bits_as_int
+    #[inline]
+    fn with_bits_set(self, bit: u8, count: u8, value: Self) -> Self {
+        let mask = Self::mask(bit, count);
+        self.clear_flag(mask) | ((value << Self::from(bit)) & mask)
+    }
+
+    /// Returns an iterator over the indices of the set bits, from least- to
+    /// most-significant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitops::BitOps;
+    ///
+    /// let bits: Vec<u8> = 0b1010.set_bits().collect();
+    /// assert_eq!(bits, vec![1, 3]);
+    /// ```
+    #[inline]
+    fn set_bits(&self) -> SetBits<Self> {
+        SetBits { v: *self }
+    }
+
+    /// Returns the number of set bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitops::BitOps;
+    ///
+    /// assert_eq!(0b1010_1011.count_set_bits(), 5);
+    /// ```
+    #[inline]
+    fn count_set_bits(&self) -> u32 {
+        let mut v = *self;
+        let mut count = 0;
+        while v != Self::zero() {
+            v = v & (v - Self::one());
+            count += 1;
+        }
+        count
+    }
+
+    /// Returns the index of the lowest set bit, or `None` if no bits are set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitops::BitOps;
+    ///
+    /// assert_eq!(0b0101_0000.find_first_set(), Some(4));
+    /// assert_eq!(0.find_first_set(), None);
+    /// ```
+    #[inline]
+    fn find_first_set(&self) -> Option<u8> {
+        if *self == Self::zero() {
+            return None;
+        }
+        let lsb = *self ^ (*self & (*self - Self::one()));
+        let mut index = 0;
+        let mut shifted = lsb;
+        while shifted != Self::one() {
+            shifted = shifted >> Self::one();
+            index += 1;
+        }
+        Some(index)
+    }
+
+    /// Returns the index of the highest set bit, or `None` if no bits are set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitops::BitOps;
+    ///
+    /// assert_eq!(0b0101_0000.find_last_set(), Some(6));
+    /// assert_eq!(0.find_last_set(), None);
+    /// ```
+    #[inline]
+    fn find_last_set(&self) -> Option<u8> {
+        if *self == Self::zero() {
+            return None;
+        }
+        let mut v = *self;
+        let mut index = 0;
+        while v != Self::one() {
+            v = v >> Self::one();
+            index += 1;
+        }
+        Some(index)
+    }
+
+    /// Returns this number with the given bit set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit` is greater than the number of bits in this Integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitops::BitOps;
+    ///
+    /// assert_eq!(0b0000.set_bit(3), 0b1000);
+    /// ```
+    #[inline]
+    fn set_bit(self, bit: u8) -> Self {
+        self.set_flag(Self::single_bit(bit))
+    }
+
+    /// Returns this number with the given bit cleared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit` is greater than the number of bits in this Integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitops::BitOps;
+    ///
+    /// assert_eq!(0b1000.clear_bit(3), 0b0000);
+    /// ```
+    #[inline]
+    fn clear_bit(self, bit: u8) -> Self {
+        self.clear_flag(Self::single_bit(bit))
+    }
+
+    /// Returns this number with the given bit toggled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit` is greater than the number of bits in this Integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitops::BitOps;
+    ///
+    /// assert_eq!(0b1000.toggle_bit(3), 0b0000);
+    /// assert_eq!(0b0000.toggle_bit(3), 0b1000);
+    /// ```
+    #[inline]
+    fn toggle_bit(self, bit: u8) -> Self {
+        self.toggle_flag(Self::single_bit(bit))
+    }
+
+    /// Returns this number with the given flag set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitops::BitOps;
+    ///
+    /// assert_eq!(0b0000.set_flag(0b1010), 0b1010);
+    /// ```
+    #[inline]
+    fn set_flag(self, flag: Self) -> Self {
+        self | flag
+    }
+
+    /// Returns this number with the given flag cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitops::BitOps;
+    ///
+    /// assert_eq!(0b1110.clear_flag(0b1010), 0b0100);
+    /// ```
+    #[inline]
+    fn clear_flag(self, flag: Self) -> Self {
+        self ^ (self & flag)
+    }
+
+    /// Returns this number with the given flag toggled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitops::BitOps;
+    ///
+    /// assert_eq!(0b1100.toggle_flag(0b1010), 0b0110);
+    /// ```
+    #[inline]
+    fn toggle_flag(self, flag: Self) -> Self {
+        self ^ flag
+    }
+
+    /// Returns a number with only the given bit set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit` is greater than the number of bits in this Integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitops::BitOps;
+    ///
+    /// assert_eq!(u32::single_bit(3), 0b1000);
+    /// ```
+    #[inline]
+    fn single_bit(bit: u8) -> Self {
+        Self::one() << Self::from(bit)
+    }
+
+    /// Returns a number with `count` contiguous bits set, starting at `bit`.
+    ///
+    /// Returns zero if `count` is zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit + count` is greater than the number of bits in this Integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitops::BitOps;
+    ///
+    /// assert_eq!(u32::mask(4, 4), 0b1111_0000);
+    /// assert_eq!(u32::mask(4, 0), 0);
+    /// ```
+    #[inline]
+    fn mask(bit: u8, count: u8) -> Self {
+        assert!(
+            usize::from(bit) + usize::from(count) <= std::mem::size_of::<Self>() * 8,
+            "bit + count is greater than the number of bits"
+        );
+        if count == 0 {
+            return Self::zero();
+        }
+        ((Self::one() << Self::from(count)) - Self::one()) << Self::from(bit)
+    }
 }
 
 /// Implements the [`BitOps`] trait for all types that meet the requirements.
 impl<N> BitOps for N where
-    N: Copy + Integer + BitAnd<Output = Self> + Shl<Output = Self> + Shr<Output = Self> + From<u8>
+    N: Copy
+        + Integer
+        + BitAnd<Output = Self>
+        + BitOr<Output = Self>
+        + BitXor<Output = Self>
+        + Shl<Output = Self>
+        + Shr<Output = Self>
+        + From<u8>
 {
 }
 
+/// An iterator over the indices of the set bits of a [`BitOps`] integer.
+///
+/// This is returned by [`BitOps::set_bits`].
+pub struct SetBits<N> {
+    v: N,
+}
+
+impl<N: BitOps> Iterator for SetBits<N> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        if self.v == N::zero() {
+            return None;
+        }
+        let lsb = self.v ^ (self.v & (self.v - N::one()));
+        let mut index = 0;
+        let mut shifted = lsb;
+        while shifted != N::one() {
+            shifted = shifted >> N::one();
+            index += 1;
+        }
+        self.v = self.v & (self.v - N::one());
+        Some(index)
+    }
+}
+
+/// [`proptest`](https://docs.rs/proptest) integration, enabled with the `proptest` feature.
+///
+/// Generates bit-flag values whose shrinking removes individual set bits rather than
+/// decrementing the integer, so a failing case for `0b1010` shrinks towards `0b1000`,
+/// `0b0010`, or `0` instead of `0b1001`.
+#[cfg(feature = "proptest")]
+pub mod strategy {
+    use proptest::prelude::Rng;
+    use proptest::strategy::{NewTree, Strategy, ValueTree};
+    use proptest::test_runner::TestRunner;
+
+    use crate::BitOps;
+
+    /// Returns a [`Strategy`] that generates values with a random subset of the set bits
+    /// of `legal` set, shrinking by clearing one set bit at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use bitops::strategy::bit_flags;
+    /// use proptest::prelude::*;
+    ///
+    /// proptest! {
+    ///     #[test]
+    ///     fn test_flags(v in bit_flags::<u32>(0xff)) {
+    ///         assert_eq!(v & !0xff, 0);
+    ///     }
+    /// }
+    /// ```
+    pub fn bit_flags<N>(legal: N) -> BitFlags<N>
+    where
+        N: BitOps + std::fmt::Debug,
+    {
+        BitFlags { legal }
+    }
+
+    /// The [`Strategy`] returned by [`bit_flags`].
+    #[derive(Clone, Copy, Debug)]
+    pub struct BitFlags<N> {
+        legal: N,
+    }
+
+    impl<N> Strategy for BitFlags<N>
+    where
+        N: BitOps + std::fmt::Debug,
+    {
+        type Tree = BitFlagsValueTree<N>;
+        type Value = N;
+
+        fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+            let bits: Vec<u8> = self.legal.set_bits().collect();
+            let mut value = N::zero();
+            for &bit in &bits {
+                if runner.rng().random_bool(0.5) {
+                    value = value.set_bit(bit);
+                }
+            }
+            Ok(BitFlagsValueTree {
+                bits,
+                value,
+                cursor: 0,
+                last_cleared: None,
+            })
+        }
+    }
+
+    /// The [`ValueTree`] returned by [`BitFlags`], which shrinks by clearing one set
+    /// bit of the current value at a time.
+    #[derive(Clone, Debug)]
+    pub struct BitFlagsValueTree<N> {
+        bits: Vec<u8>,
+        value: N,
+        cursor: usize,
+        last_cleared: Option<u8>,
+    }
+
+    impl<N> ValueTree for BitFlagsValueTree<N>
+    where
+        N: BitOps + std::fmt::Debug,
+    {
+        type Value = N;
+
+        fn current(&self) -> N {
+            self.value
+        }
+
+        fn simplify(&mut self) -> bool {
+            while self.cursor < self.bits.len() {
+                let bit = self.bits[self.cursor];
+                self.cursor += 1;
+                if self.value.is_bit_set(bit) {
+                    self.value = self.value.clear_bit(bit);
+                    self.last_cleared = Some(bit);
+                    return true;
+                }
+            }
+            false
+        }
+
+        fn complicate(&mut self) -> bool {
+            match self.last_cleared.take() {
+                Some(bit) => {
+                    self.value = self.value.set_bit(bit);
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,4 +529,115 @@ mod tests {
     fn bits_overflow() {
         0u16.bits_as_int(16, 0);
     }
+
+    #[test]
+    fn set_bits_iter() {
+        let bits: Vec<u8> = 0b1010_1011.set_bits().collect();
+        assert_eq!(bits, vec![0, 1, 3, 5, 7]);
+    }
+
+    #[test]
+    fn set_bits_iter_zero() {
+        let bits: Vec<u8> = 0u32.set_bits().collect();
+        assert_eq!(bits, Vec::<u8>::new());
+    }
+
+    #[test]
+    fn count_set_bits() {
+        assert_eq!(0b1010_1011.count_set_bits(), 5);
+        assert_eq!(0.count_set_bits(), 0);
+    }
+
+    #[test]
+    fn find_first_set() {
+        assert_eq!(0b0101_0000.find_first_set(), Some(4));
+        assert_eq!(0.find_first_set(), None);
+    }
+
+    #[test]
+    fn find_last_set() {
+        assert_eq!(0b0101_0000.find_last_set(), Some(6));
+        assert_eq!(0.find_last_set(), None);
+    }
+
+    #[test]
+    fn with_bits_set() {
+        assert_eq!(0xab000.with_bits_set(12, 8, 0xcd), 0xcd000);
+        assert_eq!(0x00000.with_bits_set(0, 4, 0xf), 0x0000f);
+    }
+
+    #[test]
+    fn set_bit() {
+        assert_eq!(0b0000.set_bit(3), 0b1000);
+        assert_eq!(0b1000.set_bit(3), 0b1000);
+    }
+
+    #[test]
+    fn clear_bit() {
+        assert_eq!(0b1000.clear_bit(3), 0b0000);
+        assert_eq!(0b0000.clear_bit(3), 0b0000);
+    }
+
+    #[test]
+    fn toggle_bit() {
+        assert_eq!(0b1000.toggle_bit(3), 0b0000);
+        assert_eq!(0b0000.toggle_bit(3), 0b1000);
+    }
+
+    #[test]
+    fn set_flag() {
+        assert_eq!(0b0000.set_flag(0b1010), 0b1010);
+    }
+
+    #[test]
+    fn clear_flag() {
+        assert_eq!(0b1110.clear_flag(0b1010), 0b0100);
+    }
+
+    #[test]
+    fn toggle_flag() {
+        assert_eq!(0b1100.toggle_flag(0b1010), 0b0110);
+    }
+
+    #[test]
+    fn single_bit() {
+        assert_eq!(u32::single_bit(3), 0b1000);
+    }
+
+    #[test]
+    fn mask() {
+        assert_eq!(u32::mask(4, 4), 0b1111_0000);
+        assert_eq!(u32::mask(4, 0), 0);
+    }
+
+    #[cfg(feature = "proptest")]
+    mod proptest {
+        use proptest::prelude::*;
+        use proptest::test_runner::{TestError, TestRunner};
+
+        use crate::strategy::bit_flags;
+
+        proptest! {
+            #[test]
+            fn bit_flags_stays_within_legal_mask(v in bit_flags::<u32>(0xff)) {
+                prop_assert_eq!(v & !0xff, 0);
+            }
+        }
+
+        #[test]
+        fn bit_flags_shrinks_to_minimal_failing_value() {
+            let mut runner = TestRunner::default();
+            let result = runner.run(&bit_flags::<u32>(0xff), |v| {
+                if v & 0b100 == 0 {
+                    Ok(())
+                } else {
+                    Err(TestCaseError::Fail("bit 2 set".into()))
+                }
+            });
+            match result {
+                Err(TestError::Fail(_, v)) => assert_eq!(v, 0b100),
+                other => panic!("expected a minimal failing case, got {:?}", other),
+            }
+        }
+    }
 }